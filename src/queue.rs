@@ -5,6 +5,13 @@
 #[derive(Debug, PartialEq)]
 pub struct Queue<T>(Vec<T>);
 
+/// Errors returned by fallible [`Queue`] operations.
+#[derive(Debug, PartialEq)]
+pub enum QueueError {
+    /// The operation required at least one element but the queue was empty.
+    QueueEmpty,
+}
+
 impl<T> Default for Queue<T> {
     fn default() -> Self {
         Queue(Vec::default())
@@ -19,17 +26,13 @@ impl<T> Queue<T> {
 
     /// Remove an element from the queue.
     ///
-    /// # Panics
-    ///
-    /// The caller is responsible for ensuring the precondition that the stack
-    /// is not empty.
-    fn dequeue(&mut self) -> T {
-        let len = self.0.len();
-        if len == 0 {
-            panic!("cannot dequeue from an empty queue")
+    /// Returns [`QueueError::QueueEmpty`] when the queue is empty.
+    pub fn dequeue(&mut self) -> Result<T, QueueError> {
+        if self.0.is_empty() {
+            return Err(QueueError::QueueEmpty);
         }
 
-        self.0.remove(0)
+        Ok(self.0.remove(0))
     }
 
     /// Enqueue an item at the back of the queue.
@@ -38,18 +41,15 @@ impl<T> Queue<T> {
     }
 
     /// Check if the queue is empty.
-    fn is_empty(&self) -> bool {
+    pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
 
     /// Peek the value at the front of the queue.
     ///
-    /// # Panics
-    ///
-    /// The caller is responsible for ensuring the precondition that the stack
-    /// is not empty.
-    fn peek(&self) -> &T {
-        &self.0.get(0).expect("cannot peek into an empty queue")
+    /// Returns [`QueueError::QueueEmpty`] when the queue is empty.
+    fn peek(&self) -> Result<&T, QueueError> {
+        self.0.first().ok_or(QueueError::QueueEmpty)
     }
 }
 
@@ -64,10 +64,9 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "cannot peek into an empty queue")]
     fn dequeue_new_queue() {
         let queue: Queue<i32> = Queue::default();
-        let _ = queue.peek();
+        assert_eq!(queue.peek(), Err(QueueError::QueueEmpty));
     }
 
     #[test]
@@ -77,11 +76,11 @@ mod tests {
         queue.enqueue(2);
         queue.enqueue(3);
 
-        assert_eq!(queue.peek(), &1);
-        assert_eq!(queue.dequeue(), 1);
-        assert_eq!(queue.dequeue(), 2);
-        assert_eq!(queue.peek(), &3);
-        assert_eq!(queue.dequeue(), 3);
+        assert_eq!(queue.peek(), Ok(&1));
+        assert_eq!(queue.dequeue(), Ok(1));
+        assert_eq!(queue.dequeue(), Ok(2));
+        assert_eq!(queue.peek(), Ok(&3));
+        assert_eq!(queue.dequeue(), Ok(3));
         assert!(queue.is_empty());
     }
 }
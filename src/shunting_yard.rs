@@ -1,5 +1,31 @@
-use queue::Queue;
-use stack::Stack;
+use queue::{Queue, QueueError};
+use stack::{Stack, StackError};
+use stack_queue::StackQueue;
+
+/// Errors returned when an infix expression cannot be translated to RPN.
+#[derive(Debug, PartialEq)]
+enum ShuntingYardError {
+    /// A left or right parenthesis had no matching counterpart.
+    MismatchedParen,
+
+    /// The expression could not be reduced to a single value, e.g. an
+    /// operator was missing operands or operands were left over.
+    Malformed,
+}
+
+impl From<StackError> for ShuntingYardError {
+    fn from(_: StackError) -> Self {
+        // The only way the operator stack is touched while empty is when a
+        // grouping delimiter has no match.
+        ShuntingYardError::MismatchedParen
+    }
+}
+
+impl From<QueueError> for ShuntingYardError {
+    fn from(_: QueueError) -> Self {
+        ShuntingYardError::Malformed
+    }
+}
 
 #[derive(PartialEq)]
 enum Assoc {
@@ -8,6 +34,15 @@ enum Assoc {
     Right,
 }
 
+/// The role a token plays in an infix expression.
+#[derive(Debug, PartialEq)]
+enum TokenKind {
+    Operand,
+    Operator,
+    LeftParen,
+    RightParen,
+}
+
 /// Expression tokens must implement this trait.
 trait TokenProperties {
     // Return the precedence of the current token
@@ -17,6 +52,34 @@ trait TokenProperties {
     fn associativity(&self) -> Assoc {
         Assoc::Left
     }
+
+    // Return the kind of the current token.
+    //
+    // By default a token is an operand when it has no precedence and an
+    // operator otherwise; grouping tokens must override this to report
+    // themselves as a left or right parenthesis.
+    fn kind(&self) -> TokenKind {
+        if self.precedence() == 0 {
+            TokenKind::Operand
+        } else {
+            TokenKind::Operator
+        }
+    }
+}
+
+/// Tokens that can be reduced to a value implement this trait so an RPN
+/// queue can be evaluated directly.
+trait Evaluate {
+    /// The value an expression evaluates to.
+    type Value;
+
+    // Return the number of operands the token consumes. Operands have an
+    // arity of 0.
+    fn arity(&self) -> usize;
+
+    // Reduce the token and its `arity` operands to a single value. The
+    // operands are provided in application order.
+    fn apply(&self, operands: Vec<Self::Value>) -> Self::Value;
 }
 
 struct ShuntingYard<T, I>
@@ -40,42 +103,73 @@ where
     /// infix notation to produce a result in RPN.
     ///
     /// [shunting yard algorithm]: https://en.wikipedia.org/wiki/Shunting-yard_algorithm
-    fn produce_postfix(self) -> Queue<T> {
+    fn produce_postfix(self) -> Result<Queue<T>, ShuntingYardError> {
         let mut tokens = self.input.into_iter();
-        let mut parsed: Queue<T> = Queue::default();
-        let mut operators: Stack<T> = Stack::default();
+
+        // The operator stack and the output queue share a single buffer: the
+        // stack grows from one end while the RPN output is enqueued at the
+        // other, so parsing never allocates a second container.
+        let mut buffer: StackQueue<T> = StackQueue::default();
 
         while let Some(token) = tokens.next() {
-            // If the precedence is 0 then we have an operand. Operands are
-            // always enqueued.
-            if token.precedence() == 0 {
-                parsed.enqueue(token);
-                continue;
-            }
+            match token.kind() {
+                // Operands are always enqueued.
+                TokenKind::Operand => buffer.enqueue(token),
+
+                // While the operator stack is not empty, check if the
+                // current operator should be pushed on to the top.
+                //
+                // When compared to `token` -- if the operator's stack
+                // precedence is greater, or it is equal and left
+                // associative, then pop it off the stack and enqueue it. A
+                // left paren is never popped this way.
+                TokenKind::Operator => {
+                    while !buffer.is_empty() {
+                        if buffer.peek()?.kind() == TokenKind::LeftParen {
+                            break;
+                        }
 
-            // While the operator stack is not empty, check if the current
-            // operator should be pushed on to the top.
-            //
-            // When compared to `token` -- if the operator's stack
-            // precedence is greater, or it is equal and left associative,
-            // then remove it from the stack and enqueue it.
-            while !operators.is_empty() {
-                if Self::should_stack(&operators.peek(), token.precedence()) {
-                    parsed.enqueue(operators.pop())
-                } else {
-                    break;
+                        if Self::should_stack(buffer.peek()?, token.precedence()) {
+                            let operator = buffer.pop()?;
+                            buffer.enqueue(operator);
+                        } else {
+                            break;
+                        }
+                    }
+
+                    buffer.push(token);
                 }
-            }
 
-            operators.push(token);
+                // A left paren is pushed directly onto the operator stack.
+                TokenKind::LeftParen => buffer.push(token),
+
+                // A right paren pops operators into the output queue until
+                // the matching left paren is found, which is discarded
+                // along with the right paren itself. An empty stack here
+                // means the right paren has no match.
+                TokenKind::RightParen => {
+                    while buffer.peek()?.kind() != TokenKind::LeftParen {
+                        let operator = buffer.pop()?;
+                        buffer.enqueue(operator);
+                    }
+
+                    buffer.pop()?;
+                }
+            }
         }
 
-        // Enqueue the remaining operators
-        while !operators.is_empty() {
-            parsed.enqueue(operators.pop())
+        // Enqueue the remaining operators. A left paren still on the stack
+        // means it was never closed.
+        while !buffer.is_empty() {
+            let operator = buffer.pop()?;
+            if operator.kind() == TokenKind::LeftParen {
+                return Err(ShuntingYardError::MismatchedParen);
+            }
+
+            buffer.enqueue(operator);
         }
 
-        parsed
+        Ok(buffer.into_queue())
     }
 
     fn should_stack(cur_top: &T, cur_prec: i32) -> bool {
@@ -84,6 +178,39 @@ where
     }
 }
 
+impl<T, I> ShuntingYard<T, I>
+where
+    T: TokenProperties + Evaluate,
+    I: IntoIterator<Item = T>,
+{
+    /// Translate the infix expression to RPN and evaluate it to a single
+    /// value.
+    ///
+    /// Each token is dequeued in turn: its `arity` operands are popped from
+    /// the stack, the token is applied, and the result is pushed back. A
+    /// well-formed expression leaves exactly one value on the stack.
+    fn evaluate(self) -> Result<T::Value, ShuntingYardError> {
+        let mut parsed = self.produce_postfix()?;
+        let mut operands: Stack<T::Value> = Stack::default();
+
+        while !parsed.is_empty() {
+            let token = parsed.dequeue()?;
+            let arguments = operands
+                .pop_n(token.arity())
+                .ok_or(ShuntingYardError::Malformed)?;
+
+            operands.push(token.apply(arguments));
+        }
+
+        let value = operands.pop().map_err(|_| ShuntingYardError::Malformed)?;
+        if !operands.is_empty() {
+            return Err(ShuntingYardError::Malformed);
+        }
+
+        Ok(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -100,6 +227,9 @@ mod tests {
         Minus,
         Multiply,
         Plus,
+
+        LeftParen,
+        RightParen,
     }
 
     impl TokenProperties for Token {
@@ -112,6 +242,39 @@ mod tests {
                 _ => 0,
             }
         }
+
+        fn kind(&self) -> TokenKind {
+            match &self {
+                Token::Value(_) => TokenKind::Operand,
+                Token::LeftParen => TokenKind::LeftParen,
+                Token::RightParen => TokenKind::RightParen,
+
+                _ => TokenKind::Operator,
+            }
+        }
+    }
+
+    impl Evaluate for Token {
+        type Value = i64;
+
+        fn arity(&self) -> usize {
+            match &self {
+                Token::Minus | Token::Multiply | Token::Plus => 2,
+
+                _ => 0,
+            }
+        }
+
+        fn apply(&self, operands: Vec<i64>) -> i64 {
+            match &self {
+                Token::Value(Value::Int(value)) => *value,
+                Token::Minus => operands[0] - operands[1],
+                Token::Multiply => operands[0] * operands[1],
+                Token::Plus => operands[0] + operands[1],
+
+                _ => unreachable!("grouping tokens are discarded before evaluation"),
+            }
+        }
     }
 
     #[test]
@@ -136,6 +299,45 @@ mod tests {
         ];
         let shunting_yard = ShuntingYard::new(input);
 
-        assert_eq!(shunting_yard.produce_postfix(), Queue::new(output))
+        assert_eq!(shunting_yard.produce_postfix(), Ok(Queue::new(output)))
+    }
+
+    #[test]
+    fn test_shunting_yard_grouping() {
+        let input = vec![
+            Token::LeftParen,
+            Token::Value(Value::Int(1)),
+            Token::Plus,
+            Token::Value(Value::Int(2)),
+            Token::RightParen,
+            Token::Multiply,
+            Token::Value(Value::Int(3)),
+        ];
+        let output = vec![
+            Token::Value(Value::Int(1)),
+            Token::Value(Value::Int(2)),
+            Token::Plus,
+            Token::Value(Value::Int(3)),
+            Token::Multiply,
+        ];
+        let shunting_yard = ShuntingYard::new(input);
+
+        assert_eq!(shunting_yard.produce_postfix(), Ok(Queue::new(output)))
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let input = vec![
+            Token::LeftParen,
+            Token::Value(Value::Int(1)),
+            Token::Plus,
+            Token::Value(Value::Int(2)),
+            Token::RightParen,
+            Token::Multiply,
+            Token::Value(Value::Int(3)),
+        ];
+        let shunting_yard = ShuntingYard::new(input);
+
+        assert_eq!(shunting_yard.evaluate(), Ok(9))
     }
 }
@@ -1,49 +1,101 @@
-/// A generic stack that wraps a `Vec<T>`.
+/// A generic stack with support for snapshots and rewinding.
 ///
-/// These methods wrap existing methods on `Vec<T>`. The only reason this
-/// struct exists was for my own benefit of implementing.
-pub struct Stack<T>(Vec<T>);
+/// The live elements are kept in `cache`. Speculative parsing is supported
+/// through [`snapshot`], [`restore`], and [`clear_snapshot`]: `snapshot`
+/// records the current stack so a later `restore` can rewind to it, while
+/// `clear_snapshot` commits by discarding the recorded state. Snapshots
+/// nest -- each records its own state, so committing or rewinding an inner
+/// snapshot leaves any outer snapshot intact.
+///
+/// Only the snapshot methods require `T: Clone`; the core stack API
+/// (`push`, `pop`, `peek`, `pop_n`, `is_empty`) is free of the bound, so the
+/// non-speculative path never pays for a capability it does not use.
+///
+/// [`snapshot`]: Stack::snapshot
+/// [`restore`]: Stack::restore
+/// [`clear_snapshot`]: Stack::clear_snapshot
+pub struct Stack<T> {
+    cache: Vec<T>,
+    snapshots: Vec<Vec<T>>,
+}
+
+/// Errors returned by fallible [`Stack`] operations.
+#[derive(Debug, PartialEq)]
+pub enum StackError {
+    /// The operation required at least one element but the stack was empty.
+    StackEmpty,
+}
 
 impl<T> Default for Stack<T> {
     fn default() -> Self {
-        Stack(Vec::default())
+        Stack {
+            cache: Vec::default(),
+            snapshots: Vec::default(),
+        }
     }
 }
 
 impl<T> Stack<T> {
     /// Check if the stack is empty.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.cache.is_empty()
     }
 
     /// Peek the value at the top of the stack.
     ///
-    /// # Panics
-    ///
-    /// The caller is responsible for ensuring the precondition that the stack
-    /// is not empty.
-    pub fn peek(&self) -> &T {
-        let len = self.0.len();
-        if len == 0 {
-            panic!("cannot peek into an empty stack")
-        }
-
-        &self.0[len - 1]
+    /// Returns [`StackError::StackEmpty`] when the stack is empty.
+    pub fn peek(&self) -> Result<&T, StackError> {
+        self.cache.last().ok_or(StackError::StackEmpty)
     }
 
     /// Pop a value off the top of the stack.
     ///
-    /// # Panics
-    ///
-    /// The caller is responsible for ensuring the precondition that the stack
-    /// is not empty.
-    pub fn pop(&mut self) -> T {
-        self.0.pop().expect("cannot pop from an empty stack")
+    /// Returns [`StackError::StackEmpty`] when the stack is empty.
+    pub fn pop(&mut self) -> Result<T, StackError> {
+        self.cache.pop().ok_or(StackError::StackEmpty)
     }
 
     /// Push a value onto the top of the stack.
     pub fn push(&mut self, value: T) {
-        self.0.push(value)
+        self.cache.push(value)
+    }
+
+    /// Remove the top `n` items from the stack.
+    ///
+    /// The removed items are returned in application order, i.e. the item
+    /// that was deepest in the stack comes first. Returns `None` when the
+    /// stack holds fewer than `n` items and nothing is removed.
+    pub fn pop_n(&mut self, n: usize) -> Option<Vec<T>> {
+        let len = self.cache.len();
+        if n > len {
+            return None;
+        }
+
+        Some(self.cache.split_off(len - n))
+    }
+}
+
+impl<T: Clone> Stack<T> {
+    /// Take a snapshot of the current stack so it can be rewound later.
+    pub fn snapshot(&mut self) {
+        self.snapshots.push(self.cache.clone());
+    }
+
+    /// Rewind the stack to the most recent snapshot, discarding every change
+    /// made since it was taken.
+    pub fn restore(&mut self) {
+        if let Some(cache) = self.snapshots.pop() {
+            self.cache = cache;
+        }
+    }
+
+    /// Commit the most recent snapshot, discarding the state it recorded for
+    /// a potential rewind.
+    ///
+    /// Any outer snapshot keeps its own recorded state, so committing an
+    /// inner snapshot leaves outer snapshots intact.
+    pub fn clear_snapshot(&mut self) {
+        self.snapshots.pop();
     }
 }
 
@@ -58,10 +110,22 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "cannot peek into an empty stack")]
     fn peek_new_stack() {
         let stack: Stack<i32> = Stack::default();
-        stack.peek();
+        assert_eq!(stack.peek(), Err(StackError::StackEmpty));
+    }
+
+    #[test]
+    fn test_pop_n() {
+        let mut stack: Stack<i32> = Stack::default();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+
+        assert_eq!(stack.pop_n(4), None);
+        assert_eq!(stack.pop_n(2), Some(vec![2, 3]));
+        assert_eq!(stack.pop(), Ok(1));
+        assert!(stack.is_empty());
     }
 
     #[test]
@@ -71,11 +135,66 @@ mod tests {
         stack.push(2);
         stack.push(3);
 
-        assert_eq!(stack.peek(), &3);
-        assert_eq!(stack.pop(), 3);
-        assert_eq!(stack.pop(), 2);
-        assert_eq!(stack.peek(), &1);
-        assert_eq!(stack.pop(), 1);
+        assert_eq!(stack.peek(), Ok(&3));
+        assert_eq!(stack.pop(), Ok(3));
+        assert_eq!(stack.pop(), Ok(2));
+        assert_eq!(stack.peek(), Ok(&1));
+        assert_eq!(stack.pop(), Ok(1));
         assert!(stack.is_empty())
     }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let mut stack: Stack<i32> = Stack::default();
+        stack.push(1);
+        stack.push(2);
+
+        stack.snapshot();
+        stack.push(3);
+        assert_eq!(stack.pop(), Ok(3));
+        assert_eq!(stack.pop(), Ok(2));
+        assert_eq!(stack.pop(), Ok(1));
+
+        stack.restore();
+        assert_eq!(stack.pop(), Ok(2));
+        assert_eq!(stack.pop(), Ok(1));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_clear_snapshot_commits() {
+        let mut stack: Stack<i32> = Stack::default();
+        stack.push(1);
+        stack.push(2);
+
+        stack.snapshot();
+        assert_eq!(stack.pop(), Ok(2));
+        stack.clear_snapshot();
+
+        stack.restore();
+        assert_eq!(stack.pop(), Ok(1));
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn test_nested_snapshots() {
+        let mut stack: Stack<i32> = Stack::default();
+        stack.push(1);
+        stack.push(2);
+
+        stack.snapshot();
+        stack.push(3);
+        stack.snapshot();
+
+        // Pop below both snapshot baselines, then commit the inner one.
+        assert_eq!(stack.pop(), Ok(3));
+        assert_eq!(stack.pop(), Ok(2));
+        stack.clear_snapshot();
+
+        // The outer snapshot still recovers its own state.
+        stack.restore();
+        assert_eq!(stack.pop(), Ok(2));
+        assert_eq!(stack.pop(), Ok(1));
+        assert!(stack.is_empty());
+    }
 }
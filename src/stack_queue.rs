@@ -0,0 +1,236 @@
+use queue::{Queue, QueueError};
+use stack::StackError;
+
+/// A combined stack and queue backed by a single buffer.
+///
+/// Rather than allocating a separate [`Stack`] and [`Queue`], both live in
+/// one `buf`: the stack grows up from the low end and occupies `buf[..top]`,
+/// while the queue occupies `buf[front..back]` nearer the high end, leaving
+/// a gap of reusable slots in `buf[top..front]`. Every boundary operation is
+/// O(1) amortized: both regions are relocated only when a push finds the gap
+/// closed, and the gap is then grown in bulk.
+///
+/// The [`shift`] operation moves the queue head onto the top of the stack;
+/// when the gap is closed the head already sits directly above the stack, so
+/// the move is just a boundary adjustment with no data movement.
+///
+/// [`Stack`]: crate::stack::Stack
+/// [`Queue`]: crate::queue::Queue
+/// [`shift`]: StackQueue::shift
+pub struct StackQueue<T> {
+    buf: Vec<Option<T>>,
+    top: usize,
+    front: usize,
+    back: usize,
+}
+
+impl<T> Default for StackQueue<T> {
+    fn default() -> Self {
+        StackQueue {
+            buf: Vec::default(),
+            top: 0,
+            front: 0,
+            back: 0,
+        }
+    }
+}
+
+impl<T> StackQueue<T> {
+    /// Check if the stack region is empty.
+    pub fn is_empty(&self) -> bool {
+        self.top == 0
+    }
+
+    /// Peek the value at the top of the stack.
+    ///
+    /// Returns [`StackError::StackEmpty`] when the stack is empty.
+    pub fn peek(&self) -> Result<&T, StackError> {
+        if self.top == 0 {
+            return Err(StackError::StackEmpty);
+        }
+
+        self.buf[self.top - 1].as_ref().ok_or(StackError::StackEmpty)
+    }
+
+    /// Push a value onto the top of the stack, reusing a gap slot or opening
+    /// one in bulk when the gap is closed.
+    pub fn push(&mut self, value: T) {
+        if self.top == self.front {
+            self.open_gap();
+        }
+
+        self.buf[self.top] = Some(value);
+        self.top += 1;
+    }
+
+    /// Pop a value off the top of the stack.
+    ///
+    /// Returns [`StackError::StackEmpty`] when the stack is empty. The
+    /// vacated slot becomes part of the gap.
+    pub fn pop(&mut self) -> Result<T, StackError> {
+        if self.top == 0 {
+            return Err(StackError::StackEmpty);
+        }
+
+        self.top -= 1;
+        self.buf[self.top].take().ok_or(StackError::StackEmpty)
+    }
+
+    /// Enqueue a value at the back of the queue.
+    ///
+    /// The queue always occupies the high end of the buffer, so its back
+    /// coincides with the buffer end and the value simply extends it.
+    pub fn enqueue(&mut self, value: T) {
+        self.buf.push(Some(value));
+        self.back += 1;
+    }
+
+    /// Move the queue head onto the top of the stack.
+    ///
+    /// When the gap is closed the queue head already sits directly above the
+    /// stack, so the shift is just a boundary move; otherwise the head is
+    /// copied across the gap into the freed slot. Returns
+    /// [`QueueError::QueueEmpty`] when the queue is empty.
+    pub fn shift(&mut self) -> Result<(), QueueError> {
+        if self.front == self.back {
+            return Err(QueueError::QueueEmpty);
+        }
+
+        if self.top != self.front {
+            self.buf[self.top] = self.buf[self.front].take();
+        }
+        self.top += 1;
+        self.front += 1;
+
+        Ok(())
+    }
+
+    /// Remove a value from the front of the queue.
+    ///
+    /// Returns [`QueueError::QueueEmpty`] when the queue is empty. The vacated
+    /// slot becomes part of the gap.
+    pub fn dequeue(&mut self) -> Result<T, QueueError> {
+        if self.front == self.back {
+            return Err(QueueError::QueueEmpty);
+        }
+
+        let value = self.buf[self.front].take();
+        self.front += 1;
+
+        value.ok_or(QueueError::QueueEmpty)
+    }
+
+    /// Consume the buffer and collect the queue, front to back, into a
+    /// [`Queue`]. The stack region and the gap are discarded.
+    ///
+    /// [`Queue`]: crate::queue::Queue
+    pub fn into_queue(self) -> Queue<T> {
+        let mut values = Vec::with_capacity(self.back - self.front);
+        for slot in self.buf.into_iter().take(self.back).skip(self.front) {
+            if let Some(value) = slot {
+                values.push(value);
+            }
+        }
+
+        Queue::new(values)
+    }
+
+    /// Open gap slots between the stack and the queue.
+    ///
+    /// Called only when the gap is closed. The queue is relocated once and
+    /// the gap is grown in bulk so that pushes amortize to O(1).
+    fn open_gap(&mut self) {
+        let queue_len = self.back - self.front;
+        let extra = (self.top + queue_len).max(1);
+
+        let mut buf = Vec::with_capacity(self.top + extra + queue_len);
+        for slot in self.buf[..self.top].iter_mut() {
+            buf.push(slot.take());
+        }
+        for _ in 0..extra {
+            buf.push(None);
+        }
+        for slot in self.buf[self.front..self.back].iter_mut() {
+            buf.push(slot.take());
+        }
+
+        self.front = self.top + extra;
+        self.back = self.front + queue_len;
+        self.buf = buf;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_new_stack_queue() {
+        let stack_queue: StackQueue<i32> = StackQueue::default();
+        assert!(stack_queue.is_empty());
+        assert_eq!(stack_queue.peek(), Err(StackError::StackEmpty));
+    }
+
+    #[test]
+    fn test_stack_operations() {
+        let mut stack_queue: StackQueue<i32> = StackQueue::default();
+        stack_queue.push(1);
+        stack_queue.push(2);
+        stack_queue.push(3);
+
+        assert_eq!(stack_queue.peek(), Ok(&3));
+        assert_eq!(stack_queue.pop(), Ok(3));
+        assert_eq!(stack_queue.pop(), Ok(2));
+        assert_eq!(stack_queue.pop(), Ok(1));
+        assert!(stack_queue.is_empty());
+    }
+
+    #[test]
+    fn test_enqueue_and_shift() {
+        let mut stack_queue: StackQueue<i32> = StackQueue::default();
+        // A closed gap: the queue head shifts onto the stack in place.
+        stack_queue.enqueue(1);
+        stack_queue.enqueue(2);
+
+        stack_queue.shift().unwrap();
+        assert_eq!(stack_queue.peek(), Ok(&1));
+
+        // An open gap from a push: the queue head shifts across it.
+        stack_queue.push(3);
+        stack_queue.shift().unwrap();
+        assert_eq!(stack_queue.peek(), Ok(&2));
+
+        assert_eq!(stack_queue.dequeue(), Err(QueueError::QueueEmpty));
+    }
+
+    #[test]
+    fn test_into_queue() {
+        let mut stack_queue: StackQueue<i32> = StackQueue::default();
+        stack_queue.push(1);
+        stack_queue.enqueue(10);
+        stack_queue.push(2);
+        stack_queue.enqueue(20);
+
+        // The stack region and the gap are dropped; the queue survives in
+        // front-to-back order.
+        let mut queue = stack_queue.into_queue();
+        assert_eq!(queue.dequeue(), Ok(10));
+        assert_eq!(queue.dequeue(), Ok(20));
+        assert!(queue.is_empty());
+    }
+
+    #[test]
+    fn test_interleaved_stack_and_queue() {
+        let mut stack_queue: StackQueue<i32> = StackQueue::default();
+        stack_queue.push(1);
+        stack_queue.enqueue(10);
+        stack_queue.push(2);
+        stack_queue.enqueue(20);
+
+        assert_eq!(stack_queue.pop(), Ok(2));
+        assert_eq!(stack_queue.pop(), Ok(1));
+        assert_eq!(stack_queue.dequeue(), Ok(10));
+        assert_eq!(stack_queue.dequeue(), Ok(20));
+        assert_eq!(stack_queue.dequeue(), Err(QueueError::QueueEmpty));
+    }
+}